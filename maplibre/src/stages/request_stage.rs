@@ -1,5 +1,7 @@
 //! Requests tiles which are currently in view
 
+use bevy_ecs::prelude::{IntoSystemConfigs, Res, ResMut, Resource, Schedule};
+
 use crate::coords::ZoomLevel;
 use crate::io::apc::{AsyncProcedureCall, Context, Input};
 use crate::io::pipeline::PipelineContext;
@@ -7,7 +9,7 @@ use crate::io::pipeline::Processable;
 use crate::io::tile_pipelines::build_vector_tile_pipeline;
 use crate::stages::HeadedPipelineProcessor;
 use crate::{
-    context::MapContext,
+    context::ViewState,
     coords::{ViewRegion, WorldTileCoords},
     error::Error,
     io::{
@@ -15,7 +17,6 @@ use crate::{
         tile_repository::TileRepository,
         TileRequest,
     },
-    schedule::Stage,
     Environment, HttpClient, Scheduler, Style,
 };
 use std::borrow::Borrow;
@@ -28,6 +29,10 @@ use std::process::Output;
 use std::rc::Rc;
 use std::str::FromStr;
 
+/// State for the tile-request system. The former `MapContext` fields it reads
+/// (`ViewState`, `Style`, `TileRepository`) now live in the ECS `World` as
+/// `Resource`s, so only the stage's own handles are stored here.
+#[derive(Resource)]
 pub struct RequestStage<E: Environment> {
     apc: Rc<RefCell<E::AsyncProcedureCall>>,
     http_source_client: HttpSourceClient<E::HttpClient>,
@@ -38,6 +43,11 @@ impl<E: Environment> RequestStage<E> {
         http_source_client: HttpSourceClient<E::HttpClient>,
         apc: Rc<RefCell<E::AsyncProcedureCall>>,
     ) -> Self {
+        // On the web target, drive the APC queue off the frame loop so large tile
+        // batches tessellate without stalling `requestAnimationFrame`.
+        #[cfg(target_arch = "wasm32")]
+        spawn_scheduler_loop::<E>(apc.clone());
+
         Self {
             apc,
             http_source_client,
@@ -45,28 +55,43 @@ impl<E: Environment> RequestStage<E> {
     }
 }
 
-impl<E: Environment> Stage for RequestStage<E> {
-    fn run(
-        &mut self,
-        MapContext {
-            view_state,
-            style,
-            tile_repository,
-            ..
-        }: &mut MapContext,
-    ) {
-        let view_region = view_state.create_view_region();
-
-        if view_state.camera.did_change(0.05) || view_state.zoom.did_change(0.05) {
-            if let Some(view_region) = &view_region {
-                // FIXME: We also need to request tiles from layers above if we are over the maximum zoom level
-                self.request_tiles_in_view(tile_repository, style, view_region);
-            }
-        }
+/// Run-condition for [`request_stage_system`]: only run while the camera or
+/// zoom is actually interpolating, so idle frames skip tile requests entirely.
+pub fn camera_did_change(view_state: Res<ViewState>) -> bool {
+    view_state.camera.did_change(0.05) || view_state.zoom.did_change(0.05)
+}
 
-        view_state.camera.update_reference();
-        view_state.zoom.update_reference();
+/// Request tiles which are currently in view.
+///
+/// This is the `bevy_ecs` system form of the old `Stage::run`: the resources it
+/// touches are declared in the signature instead of being threaded through
+/// `MapContext` by hand, which lets the scheduler order it against the other
+/// stages and skip it via [`camera_did_change`].
+pub fn request_stage_system<E: Environment>(
+    stage: Res<RequestStage<E>>,
+    mut view_state: ResMut<ViewState>,
+    style: Res<Style>,
+    mut tile_repository: ResMut<TileRepository>,
+) {
+    if let Some(view_region) = view_state.create_view_region() {
+        // FIXME: We also need to request tiles from layers above if we are over the maximum zoom level
+        stage.request_tiles_in_view(&mut tile_repository, &style, &view_region);
     }
+
+    view_state.camera.update_reference();
+    view_state.zoom.update_reference();
+}
+
+/// Build the [`Schedule`] which ticks the staging pipeline once per frame.
+///
+/// User-added stages can be appended to the returned schedule without threading
+/// new fields through `MapContext`. The schedule is not run from here: the
+/// caller ticks it with `schedule.run(world)`, and the winit loop does not call
+/// this function.
+pub fn build_schedule<E: Environment>() -> Schedule {
+    let mut schedule = Schedule::default();
+    schedule.add_systems(request_stage_system::<E>.run_if(camera_did_change));
+    schedule
 }
 
 pub fn schedule<E: Environment>(
@@ -101,6 +126,70 @@ pub fn schedule<E: Environment>(
     })
 }
 
+/// Number of completed pipeline results drained from the
+/// [`AsyncProcedureCall`] queue per scheduler tick on the web target.
+#[cfg(target_arch = "wasm32")]
+const DEFAULT_TILE_BUDGET_PER_TICK: usize = 8;
+
+/// Milliseconds between two scheduler ticks on the web target.
+#[cfg(target_arch = "wasm32")]
+const SCHEDULER_TICK_INTERVAL_MS: i32 = 16;
+
+/// Spawn a `setTimeout`-driven loop which drains the [`AsyncProcedureCall`]
+/// queue independently of `requestAnimationFrame`/`RedrawRequested`.
+///
+/// On the web target the pipeline futures would otherwise resolve inside the
+/// render path, so tessellating a large batch of tiles stalls frames. Draining
+/// at most `budget` completed results per tick off the frame loop keeps input
+/// and rendering smooth; `AsyncProcedureCall::receive` applies each result into
+/// the [`TileRepository`], which the render loop then only consumes.
+#[cfg(target_arch = "wasm32")]
+pub fn spawn_scheduler_loop<E: Environment>(apc: Rc<RefCell<E::AsyncProcedureCall>>) {
+    spawn_scheduler_loop_with_budget::<E>(apc, DEFAULT_TILE_BUDGET_PER_TICK);
+}
+
+/// Like [`spawn_scheduler_loop`] but with a caller-provided per-tick budget.
+#[cfg(target_arch = "wasm32")]
+pub fn spawn_scheduler_loop_with_budget<E: Environment>(
+    apc: Rc<RefCell<E::AsyncProcedureCall>>,
+    budget: usize,
+) {
+    use wasm_bindgen::prelude::Closure;
+    use wasm_bindgen::JsCast;
+
+    fn set_timeout(closure: &Closure<dyn FnMut()>, timeout_ms: i32) {
+        web_sys::window()
+            .unwrap()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(
+                closure.as_ref().unchecked_ref(),
+                timeout_ms,
+            )
+            .expect("failed to schedule scheduler tick");
+    }
+
+    // The closure re-arms itself so the loop keeps ticking for the lifetime of
+    // the map. It is kept alive by moving it into its own body via an `Rc`.
+    let holder: Rc<RefCell<Option<Closure<dyn FnMut()>>>> = Rc::new(RefCell::new(None));
+    let holder_inner = holder.clone();
+
+    *holder.borrow_mut() = Some(Closure::wrap(Box::new(move || {
+        for _ in 0..budget {
+            // `receive` applies one completed result into the `TileRepository`
+            // and returns `None` once the queue is drained for this tick.
+            if apc.deref().borrow().receive().is_none() {
+                break;
+            }
+        }
+
+        set_timeout(
+            holder_inner.borrow().as_ref().unwrap(),
+            SCHEDULER_TICK_INTERVAL_MS,
+        );
+    }) as Box<dyn FnMut()>));
+
+    set_timeout(holder.borrow().as_ref().unwrap(), SCHEDULER_TICK_INTERVAL_MS);
+}
+
 impl<E: Environment> RequestStage<E> {
     /// Request tiles which are currently in view.
     #[tracing::instrument(skip_all)]
@@ -3,8 +3,11 @@
 //! * Platform Events like suspend/resume
 //! * Render a new frame
 
-use winit::event::{ElementState, Event, KeyboardInput, VirtualKeyCode, WindowEvent};
-use winit::event_loop::{ControlFlow, EventLoop};
+use gilrs::{Axis, Button, Gilrs};
+use winit::event::{
+    DeviceEvent, ElementState, Event, KeyboardInput, MouseScrollDelta, VirtualKeyCode, WindowEvent,
+};
+use winit::event_loop::{ControlFlow, EventLoopBuilder};
 use winit::window::WindowBuilder;
 
 use style_spec::Style;
@@ -16,14 +19,157 @@ use crate::platform::Instant;
 use crate::render::render_state::RenderState;
 use crate::{FromCanvas, FromWindow, MapBuilder, WindowSize};
 
-impl Runnable<winit::event_loop::EventLoop<()>> for MapState<winit::window::Window> {
-    fn run(mut self, event_loop: winit::event_loop::EventLoop<()>, max_frames: Option<u64>) {
+/// A user event which can be pushed onto the winit event loop through an
+/// [`winit::event_loop::EventLoopProxy`] to wake a [`ControlFlow::Wait`] loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WakeEvent {
+    /// A tile finished loading and dirtied the scene, so the map must be
+    /// redrawn even though no `WindowEvent` occurred.
+    TileReady,
+}
+
+/// Per-axis sensitivity and dead-zone configuration for the gamepad.
+#[derive(Copy, Clone, Debug)]
+pub struct GamepadConfig {
+    /// Stick deflections with a magnitude below this are ignored, filtering out
+    /// the resting jitter of analog sticks.
+    pub dead_zone: f64,
+    /// Pan speed in logical pixels per second at full left-stick deflection.
+    pub pan_sensitivity: f64,
+    /// Zoom speed (scroll lines per second) at full right-stick / trigger deflection.
+    pub zoom_sensitivity: f64,
+    /// Rotate/tilt speed (scroll lines per second) while a bumper is held.
+    pub rotate_sensitivity: f64,
+}
+
+impl Default for GamepadConfig {
+    fn default() -> Self {
+        Self {
+            dead_zone: 0.15,
+            pan_sensitivity: 600.0,
+            zoom_sensitivity: 8.0,
+            rotate_sensitivity: 4.0,
+        }
+    }
+}
+
+/// A pluggable analog-gamepad input source, polled on every `MainEventsCleared`
+/// (also while the loop idles, when no frame is drawn) before the
+/// keyboard/mouse [`InputController`] updates the camera.
+///
+/// It does not touch the camera directly: it synthesises the same
+/// [`DeviceEvent`]s the mouse produces and feeds them through
+/// [`InputController::device_input`], so a controller drives exactly the same
+/// pan/zoom path as a pointer — left stick pans, right stick / triggers zoom,
+/// and bumpers rotate/tilt. This gives console/TV and handheld users a way to
+/// navigate the map where no pointer exists.
+pub struct GamepadController {
+    gilrs: Gilrs,
+    config: GamepadConfig,
+}
+
+impl GamepadController {
+    pub fn new(config: GamepadConfig) -> Option<Self> {
+        Gilrs::new().ok().map(|gilrs| Self { gilrs, config })
+    }
+
+    /// Apply a dead-zone to a raw stick axis value in `[-1.0, 1.0]`.
+    fn filter(&self, value: f64) -> f64 {
+        if value.abs() < self.config.dead_zone {
+            0.0
+        } else {
+            value
+        }
+    }
+
+    /// Poll the first connected gamepad and feed its analog state into the
+    /// [`InputController`] as the equivalent synthesised mouse deltas.
+    ///
+    /// Returns `true` when the controller produced movement this tick, so the
+    /// caller can keep the loop out of `ControlFlow::Wait` while a stick is
+    /// deflected — gilrs activity does not itself generate winit events.
+    fn update_state(
+        &mut self,
+        input_controller: &mut InputController,
+        dt: std::time::Duration,
+    ) -> bool {
+        // Drain pending events so gilrs keeps its per-gamepad state current.
+        while self.gilrs.next_event().is_some() {}
+
+        let Some((_, gamepad)) = self.gilrs.gamepads().next() else {
+            return false;
+        };
+
+        let left_x = self.filter(gamepad.value(Axis::LeftStickX) as f64);
+        let left_y = self.filter(gamepad.value(Axis::LeftStickY) as f64);
+        let right_y = self.filter(gamepad.value(Axis::RightStickY) as f64);
+
+        let dt_secs = dt.as_secs_f64();
+        let mut active = false;
+
+        // Left stick pans: feed a mouse-motion delta, inverting the stick's
+        // upward-positive vertical axis to match screen space.
+        if left_x != 0.0 || left_y != 0.0 {
+            let pan = self.config.pan_sensitivity * dt_secs;
+            input_controller.device_input(&DeviceEvent::MouseMotion {
+                delta: (left_x * pan, -left_y * pan),
+            });
+            active = true;
+        }
+
+        // Right stick / triggers zoom: feed a vertical scroll delta.
+        let mut zoom = right_y;
+        zoom += gamepad.value(Axis::RightZ) as f64 - gamepad.value(Axis::LeftZ) as f64;
+        if zoom != 0.0 {
+            input_controller.device_input(&DeviceEvent::MouseWheel {
+                delta: MouseScrollDelta::LineDelta(
+                    0.0,
+                    (zoom * self.config.zoom_sensitivity * dt_secs) as f32,
+                ),
+            });
+            active = true;
+        }
+
+        // Bumpers rotate/tilt: feed a horizontal scroll delta.
+        let rotate = self.config.rotate_sensitivity * dt_secs;
+        let mut rotate_delta = 0.0;
+        if gamepad.is_pressed(Button::LeftTrigger) {
+            rotate_delta -= rotate;
+        }
+        if gamepad.is_pressed(Button::RightTrigger) {
+            rotate_delta += rotate;
+        }
+        if rotate_delta != 0.0 {
+            input_controller.device_input(&DeviceEvent::MouseWheel {
+                delta: MouseScrollDelta::LineDelta(rotate_delta as f32, 0.0),
+            });
+            active = true;
+        }
+
+        active
+    }
+}
+
+impl Runnable<winit::event_loop::EventLoop<WakeEvent>> for MapState<winit::window::Window> {
+    fn run(mut self, event_loop: winit::event_loop::EventLoop<WakeEvent>, max_frames: Option<u64>) {
         let mut last_render_time = Instant::now();
         let mut current_frame: u64 = 0;
 
         let mut input_controller = InputController::new(0.2, 100.0, 0.1);
+        let mut gamepad_controller = GamepadController::new(GamepadConfig::default());
+        let mut last_gamepad_poll = Instant::now();
 
-        event_loop.run(move |event, _, control_flow| {
+        // Hand a redraw notifier to the map state. `MapState` forwards it to the
+        // `AsyncProcedureCall`, which invokes it from the tile-completion path so a
+        // tile resolving off the frame loop sends `WakeEvent::TileReady` and wakes
+        // us out of `ControlFlow::Wait`. While any request is still in flight
+        // `needs_redraw()` stays true, so the loop never parks with pending tiles.
+        let proxy = event_loop.create_proxy();
+        self.set_redraw_notifier(Box::new(move || {
+            let _ = proxy.send_event(WakeEvent::TileReady);
+        }));
+
+        event_loop.run(move |event, _event_loop_target, control_flow| {
                 match event {
                     Event::DeviceEvent {
                         ref event,
@@ -57,6 +203,13 @@ impl Runnable<winit::event_loop::EventLoop<()>> for MapState<winit::window::Wind
                                 _ => {}
                             }
                         }
+                        // Any genuine window event may have moved the camera or
+                        // resized the surface, so redraw once to reflect it.
+                        self.window().request_redraw();
+                    }
+                    Event::UserEvent(WakeEvent::TileReady) => {
+                        // A newly fetched tile dirtied the scene while we were idle.
+                        self.window().request_redraw();
                     }
                     Event::RedrawRequested(_) => {
                         let _span_ = tracing::span!(tracing::Level::TRACE, "redraw requested").entered();
@@ -94,18 +247,66 @@ impl Runnable<winit::event_loop::EventLoop<()>> for MapState<winit::window::Wind
                         tracy_client::finish_continuous_frame!();
                     }
                     Event::Suspended => {
+                        // On Android the native window backing the wgpu surface is
+                        // destroyed here, so the surface must be dropped before the
+                        // platform reclaims it. The window handle is kept so it can be
+                        // reattached on the next `Resumed`.
                         self.suspend();
                     }
                     Event::Resumed => {
+                        // On Android the native window does not exist until the first
+                        // `Resumed`, so it is created lazily here from the now-valid
+                        // event-loop target rather than eagerly at build time (which
+                        // would panic, since there is no native window to bind to before
+                        // the activity resumes). On later resumes the window already
+                        // exists and only the surface is recreated.
+                        #[cfg(target_os = "android")]
+                        if !self.has_window() {
+                            // Build the window with the title stashed by
+                            // `MapBuilder::deferred` and attach it to the map state.
+                            self.create_window(_event_loop_target);
+                        }
+
+                        // Re-derive the real size from the now-valid window instead of
+                        // the fake size used to bootstrap `MapState`.
                         self.recreate_surface();
                         let size = self.window().inner_size();
-                        self.resize(size.width, size.height);// FIXME: Resumed is also called when the app launches for the first time. Instead of first using a "fake" inner_size() in State::new we should initialize with a proper size from the beginning
+                        self.resize(size.width, size.height);
                         self.resume();
                     }
                     Event::MainEventsCleared => {
-                        // RedrawRequested will only trigger once, unless we manually
-                        // request it.
-                        self.window().request_redraw();
+                        // Sample the gamepad here rather than in `RedrawRequested`: an
+                        // idle loop never reaches a redraw, and gilrs activity does not
+                        // raise winit events, so polling from the redraw path would leave
+                        // controller navigation dead on a still map. Feeding the deltas
+                        // now also lets a stick deflection mark the frame dirty.
+                        let gamepad_active = if let Some(gamepad_controller) =
+                            gamepad_controller.as_mut()
+                        {
+                            let now = Instant::now();
+                            let dt = now - last_gamepad_poll;
+                            last_gamepad_poll = now;
+                            gamepad_controller.update_state(&mut input_controller, dt)
+                        } else {
+                            false
+                        };
+
+                        // Only drive the loop at full speed while something is actually
+                        // moving: the camera or zoom is interpolating, input is active, a
+                        // gamepad stick is deflected, or a tile request just resolved and
+                        // dirtied the scene. Otherwise park — but when a gamepad is present
+                        // we cannot fully sleep, since stick motion raises no winit event,
+                        // so poll it again shortly via `WaitUntil`.
+                        if self.needs_redraw() || gamepad_active {
+                            *control_flow = ControlFlow::Poll;
+                            self.window().request_redraw();
+                        } else if gamepad_controller.is_some() {
+                            *control_flow = ControlFlow::WaitUntil(
+                                Instant::now() + std::time::Duration::from_millis(16),
+                            );
+                        } else {
+                            *control_flow = ControlFlow::Wait;
+                        }
                     }
                     _ => {}
                 }
@@ -113,10 +314,10 @@ impl Runnable<winit::event_loop::EventLoop<()>> for MapState<winit::window::Wind
     }
 }
 
-#[cfg(not(target_arch = "wasm32"))]
-impl FromWindow for MapBuilder<winit::window::Window, winit::event_loop::EventLoop<()>> {
+#[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+impl FromWindow for MapBuilder<winit::window::Window, winit::event_loop::EventLoop<WakeEvent>> {
     fn from_window(title: &'static str) -> Self {
-        let event_loop = EventLoop::new();
+        let event_loop = EventLoopBuilder::<WakeEvent>::with_user_event().build();
         Self::new(Box::new(move || {
             let window = WindowBuilder::new()
                 .with_title(title)
@@ -135,6 +336,48 @@ impl FromWindow for MapBuilder<winit::window::Window, winit::event_loop::EventLo
     }
 }
 
+#[cfg(target_os = "android")]
+impl MapBuilder<winit::window::Window, winit::event_loop::EventLoop<WakeEvent>> {
+    /// Android entry path.
+    ///
+    /// Unlike [`from_window`](FromWindow::from_window), the `winit` window (and
+    /// hence the wgpu surface) does not exist until the first `Event::Resumed`,
+    /// and is destroyed again on `Event::Suspended`. The window is therefore
+    /// **not** built here: [`deferred`](MapBuilder::deferred) produces a
+    /// windowless [`MapState`] bound to the Android event loop, and the run loop
+    /// creates the window from the event-loop target on the first `Resumed` (see
+    /// the `Event::Resumed` arm) and drops the surface again on `Suspended`.
+    /// Building eagerly as the desktop path does would panic, since there is no
+    /// native window to bind to before the activity resumes.
+    pub fn from_android(
+        app: winit::platform::android::activity::AndroidApp,
+        title: &'static str,
+    ) -> Self {
+        use winit::platform::android::EventLoopBuilderExtAndroid;
+
+        let event_loop = EventLoopBuilder::<WakeEvent>::with_user_event()
+            .with_android_app(app)
+            .build();
+
+        Self::deferred(event_loop, title)
+    }
+}
+
+/// Android `NativeActivity` entry point.
+///
+/// The `android-activity` glue invokes a `#[no_mangle] android_main` symbol
+/// with the live [`AndroidApp`]. This function is not marked `#[no_mangle]`
+/// itself: an application calls it from its own `#[no_mangle] android_main` to
+/// boot the map on-device.
+///
+/// [`AndroidApp`]: winit::platform::android::activity::AndroidApp
+#[cfg(target_os = "android")]
+pub fn android_main(app: winit::platform::android::activity::AndroidApp) {
+    MapBuilder::from_android(app, "maplibre")
+        .build()
+        .run(None);
+}
+
 #[cfg(target_arch = "wasm32")]
 pub fn get_body_size() -> Option<winit::dpi::LogicalSize<i32>> {
     let web_window: web_sys::Window = web_sys::window().unwrap();
@@ -160,9 +403,9 @@ pub fn get_canvas(element_id: &'static str) -> web_sys::HtmlCanvasElement {
 }
 
 #[cfg(target_arch = "wasm32")]
-impl FromCanvas for MapBuilder<winit::window::Window, winit::event_loop::EventLoop<()>> {
+impl FromCanvas for MapBuilder<winit::window::Window, winit::event_loop::EventLoop<WakeEvent>> {
     fn from_canvas(dom_id: &'static str) -> Self {
-        let event_loop = EventLoop::new();
+        let event_loop = EventLoopBuilder::<WakeEvent>::with_user_event().build();
         Self::new(Box::new(move || {
             use winit::platform::web::WindowBuilderExtWebSys;
 